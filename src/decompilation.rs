@@ -2,95 +2,217 @@
 //!
 //! Handles the extraction, decompression and  decompilation of _.apks_
 
+use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
-use std::time::Instant;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use std::io::{Read, Write};
 use std::process::{Command, exit};
-use colored::Colorize;
+use log::{debug, error};
 use zip::ZipArchive;
+use zip::write::{FileOptions, ZipWriter};
 
-use {Error, Config, print_error, print_warning};
+use {Error, Config};
+use logger;
 use results::Benchmark;
 
+/// Checks whether the given zip entry name is a multidex classes file, i.e. `classes.dex`,
+/// `classes2.dex`, `classes3.dex`, …
+fn is_dex_entry<S: AsRef<str>>(name: S) -> bool {
+    dex_index(name.as_ref()).is_some()
+}
+
+/// Returns the multidex index of a `classesN.dex` entry name (`classes.dex` is `0`,
+/// `classes2.dex` is `2`, …), or `None` if `name` isn't a multidex classes file.
+fn dex_index(name: &str) -> Option<u32> {
+    match name.strip_prefix("classes").and_then(|s| s.strip_suffix(".dex")) {
+        Some("") => Some(0),
+        Some(middle) => middle.parse::<u32>().ok(),
+        None => None,
+    }
+}
+
+/// Record of a single external tool invocation (_apktool_, _dex2jar_, _jd\_cmd_, …).
+///
+/// Keeping the full command line and captured output around turns an opaque "the command
+/// failed" abort into an actionable diagnostic. The command line is logged at debug level, and
+/// together with the captured stderr at error level when the command fails. Only its elapsed
+/// time makes it further, into a [`Benchmark`]; nothing else is threaded into `Results`/the
+/// generated reports.
+#[derive(Debug, Clone)]
+pub struct ToolInvocation {
+    /// Path or name of the executed binary.
+    binary: String,
+    /// Arguments passed to the binary.
+    args: Vec<String>,
+    /// Exit code of the process, when it terminated normally.
+    exit_code: Option<i32>,
+    /// Captured standard output.
+    stdout: String,
+    /// Captured standard error.
+    stderr: String,
+    /// Time taken to run the command.
+    elapsed: Duration,
+}
+
+impl ToolInvocation {
+    /// Returns the full, reproducible command line for this invocation.
+    pub fn command_line(&self) -> String {
+        let mut line = self.binary.clone();
+        for arg in &self.args {
+            line.push(' ');
+            line.push_str(arg);
+        }
+        line
+    }
+
+    /// Time taken to run the command.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Captured standard output.
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    /// Captured standard error.
+    pub fn stderr(&self) -> &str {
+        &self.stderr
+    }
+
+    /// Exit code of the process, when it terminated normally.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+}
+
+/// Checks whether `binary` refers to the `java` executable, as opposed to another external
+/// tool such as the dex2jar scripts.
+fn is_java(binary: &Path) -> bool {
+    binary.file_stem().and_then(|stem| stem.to_str()) == Some("java")
+}
+
+/// Runs an external tool, capturing the full invocation for diagnostics and classifying common
+/// failures instead of collapsing everything into `Error::Unknown`.
+fn run_tool(binary: &Path, args: &[String], description: &str) -> Result<ToolInvocation, Error> {
+    let start_time = Instant::now();
+    let output = Command::new(binary).args(args).output();
+    let elapsed = start_time.elapsed();
+
+    let output = match output {
+        Ok(o) => o,
+        Err(e) => {
+            error!("There was an error when executing {}: {}", description, e);
+            return Err(if e.kind() == io::ErrorKind::NotFound {
+                if is_java(binary) {
+                    Error::MissingJava
+                } else {
+                    Error::MissingTool
+                }
+            } else {
+                Error::Unknown
+            });
+        }
+    };
+
+    let invocation = ToolInvocation {
+        binary: binary.display().to_string(),
+        args: args.to_vec(),
+        exit_code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        elapsed,
+    };
+
+    debug!("Ran `{}` in {:?}", invocation.command_line(), invocation.elapsed());
+
+    if !output.status.success() {
+        let err = classify_failure(&invocation);
+        error!("{} returned an error (`{}`). More info: {}",
+               description,
+               invocation.command_line(),
+               invocation.stderr());
+        return Err(err);
+    }
+
+    Ok(invocation)
+}
+
+/// Classifies a failed tool invocation's captured output into a more specific [`Error`] than
+/// `Error::Unknown`.
+fn classify_failure(invocation: &ToolInvocation) -> Error {
+    let stderr = invocation.stderr().to_lowercase();
+
+    if stderr.contains("could not create the java virtual machine") ||
+        stderr.contains("outofmemoryerror") {
+        Error::OutOfMemory
+    } else if stderr.contains("unsupported dex version") ||
+        stderr.contains("unsupported class file version") {
+        Error::UnsupportedDexVersion
+    } else if stderr.contains("zip end header not found") ||
+        stderr.contains("central directory") || stderr.contains("encrypted") {
+        Error::CorruptApk
+    } else {
+        Error::Unknown
+    }
+}
+
 /// Decompresses the application using _Apktool_.
-pub fn decompress<S: AsRef<str>>(config: &Config, package: S) {
+pub fn decompress<S: AsRef<str>>(config: &Config, package: S, benchmarks: &mut Vec<Benchmark>) {
     let path = config.get_dist_folder().join(package.as_ref());
+
+    logger::init(config.is_verbose(), config.is_quiet());
+    let log_dir = if config.is_log_to_file() { Some(path.as_path()) } else { None };
+    logger::set_log_file(log_dir);
+
     if !path.exists() || config.is_force() {
         if path.exists() {
-            if config.is_verbose() {
-                println!("The application decompression folder exists. But no more…");
-            }
+            debug!("The application decompression folder exists. But no more…");
 
             if let Err(e) = fs::remove_dir_all(&path) {
-                print_warning(format!("There was an error when removing the decompression \
-                                       folder: {}",
-                                      e),
-                              config.is_verbose());
+                error!("There was an error when removing the decompression folder: {}", e);
             }
         }
 
-        if config.is_verbose() {
-            println!("");
-            println!("Decompressing the application…");
-        }
+        debug!("Decompressing the application…");
 
         // Command to decompress the .apk.
         // d to decode
         // -s to skip the disassembly of .dex files
         // "-o path" to specify an output directory
         // -f to force overwritting existing files
-        let output = Command::new("java")
-            .arg("-jar")
-            .arg(config.get_apktool_file())
-            .arg("d")
-            .arg("-s")
-            .arg("-o")
-            .arg(&path)
-            .arg("-f")
-            .arg(config.get_apk_file(package))
-            .output();
-
-        let output = match output {
-            Ok(o) => o,
-            Err(e) => {
-                print_error(format!("There was an error when executing the decompression \
-                                     command: {}",
-                                    e),
-                            config.is_verbose());
-                exit(Error::Unknown.into());
-            }
+        let args = vec![
+            "-jar".to_owned(),
+            config.get_apktool_file().display().to_string(),
+            "d".to_owned(),
+            "-s".to_owned(),
+            "-o".to_owned(),
+            path.display().to_string(),
+            "-f".to_owned(),
+            config.get_apk_file(package).display().to_string(),
+        ];
+
+        let invocation = match run_tool(Path::new("java"), &args, "the decompression command") {
+            Ok(i) => i,
+            Err(e) => exit(e.into()),
         };
 
-        if !output.status.success() {
-            print_error(format!("The decompression command returned an error. More info: {}",
-                                String::from_utf8_lossy(&output.stderr[..])),
-                        config.is_verbose());
-            exit(Error::Unknown.into());
-        }
+        benchmarks.push(Benchmark::new("Apktool decompression", invocation.elapsed()));
 
-        if config.is_verbose() {
-            println!("{}",
-                     format!("The application has been decompressed in {}.",
-                             path.display())
-                         .green());
-        } else if !config.is_quiet() {
-            println!("Application decompressed.");
-        }
-    } else if config.is_verbose() {
-        println!("Seems that the application has already been decompressed. There is no need to \
-                  do it again.");
+        debug!("The application has been decompressed in {}.", path.display());
+    } else {
+        debug!("Seems that the application has already been decompressed. There is no need to \
+                do it again.");
     }
 }
 
 /// Extracts the _.dex_ files.
 pub fn extract_dex<S: AsRef<str>>(config: &Config, package: S, benchmarks: &mut Vec<Benchmark>) {
     if config.is_force() || !config.get_dist_folder().join(package.as_ref()).exists() {
-        if config.is_verbose() {
-            println!("");
-            println!("To decompile the app, first we need to extract the {} file.",
-                     ".dex".italic());
-        }
+        debug!("To decompile the app, first we need to extract the .dex files.");
 
         let start_time = Instant::now();
 
@@ -98,193 +220,303 @@ pub fn extract_dex<S: AsRef<str>>(config: &Config, package: S, benchmarks: &mut
         let zip = ZipArchive::new(match File::open(config.get_apk_file(package.as_ref())) {
             Ok(f) => f,
             Err(e) => {
-                print_error(format!("There was an error when decompressing the {} file. More \
-                                     info: {}",
-                                    ".apk".italic(),
-                                    e),
-                            config.is_verbose());
+                error!("There was an error when decompressing the .apk file. More info: {}", e);
                 exit(Error::Unknown.into());
             }
         });
         if zip.is_err() {
-            print_error(format!("There was an error when decompressing the {} file. More info: \
-                                 {}",
-                                ".apk".italic(),
-                                zip.err().unwrap()),
-                        config.is_verbose());
-            exit(Error::Unknown.into());
+            error!("There was an error when decompressing the .apk file. More info: {}",
+                   zip.err().unwrap());
+            exit(Error::CorruptApk.into());
         }
 
-        // Obtaining the clases.dex file.
+        // Large apps ship several classesN.dex files because of the 64K method limit, so we
+        // need every one of them, not just classes.dex.
         let mut zip = zip.unwrap();
-        let mut dex_file = match zip.by_name("classes.dex") {
-            Ok(f) => f,
-            Err(e) => {
-                print_error(format!("There was an error while finding the classes.dex file \
-                                     inside the {} file. More info: {}",
-                                    ".apk".italic(),
-                                    e),
-                            config.is_verbose());
+        let mut dex_names: Vec<String> = (0..zip.len())
+            .filter_map(|i| zip.by_index(i).ok().map(|f| f.name().to_owned()))
+            .filter(is_dex_entry)
+            .collect();
+        dex_names.sort_by_key(|name| dex_index(name).unwrap_or(0));
+
+        if dex_names.is_empty() {
+            error!("There was an error while finding the classes.dex file inside the .apk file.");
+            exit(Error::CorruptApk.into());
+        }
+
+        // Placing every classesN.dex file into the dist_folder.
+        for dex_name in &dex_names {
+            let mut dex_file = match zip.by_name(dex_name) {
+                Ok(f) => f,
+                Err(e) => {
+                    error!("There was an error while finding the {} file inside the .apk file. \
+                           More info: {}",
+                          dex_name,
+                          e);
+                    exit(Error::CorruptApk.into());
+                }
+            };
+
+            let mut out_file = match File::create(config.get_dist_folder()
+                .join(package.as_ref())
+                .join(dex_name)) {
+                Ok(f) => f,
+                Err(e) => {
+                    error!("There was an error while creating {} file. More info: {}", dex_name, e);
+                    exit(Error::Unknown.into());
+                }
+            };
+
+            // Reading the classesN.dex file.
+            let mut bytes = Vec::with_capacity(dex_file.size() as usize);
+            if let Err(e) = dex_file.read_to_end(&mut bytes) {
+                error!("There was an error while reading {} file from the .apk. More info: {}",
+                      dex_name,
+                      e);
                 exit(Error::Unknown.into());
             }
-        };
 
-        // Placing the classes.dex file into the dist_folder.
-        let mut out_file = match File::create(config.get_dist_folder()
-            .join(package.as_ref())
-            .join("classes.dex")) {
-            Ok(f) => f,
-            Err(e) => {
-                print_error(format!("There was an error while creating classes.dex file. More \
-                                     info: {}",
-                                    e),
-                            config.is_verbose());
+            if let Err(e) = out_file.write_all(&bytes[..]) {
+                error!("There was an error while writting {} file. More info: {}", dex_name, e);
                 exit(Error::Unknown.into());
             }
+        }
+
+        benchmarks.push(Benchmark::new("Dex extraction", start_time.elapsed()));
+
+        debug!("{} .dex file(s) were extracted successfully!", dex_names.len());
+        debug!("Now it's time to create the .jar file from its classes.");
+
+        // Converting every .dex to .jar, merging them if there is more than one.
+        dex_to_jar(config, package.as_ref(), &dex_names, benchmarks);
+    } else {
+        debug!("Seems that there is already a .jar file for the application. There is no need \
+                to create it again.");
+    }
+}
+
+/// Converts _.dex_ files to _.jar_ using _Dex2jar_, merging multi-dex APKs into a single
+/// `classes.jar` so that the rest of the pipeline keeps seeing one archive.
+fn dex_to_jar<S: AsRef<str>>(
+    config: &Config,
+    package: S,
+    dex_names: &[String],
+    benchmarks: &mut Vec<Benchmark>,
+) {
+    let dex2jar_script = config.get_dex2jar_folder()
+        .join(if cfg!(target_family = "windows") {
+            "d2j-dex2jar.bat"
+        } else {
+            "d2j-dex2jar.sh"
+        });
+
+    let mut jars = Vec::with_capacity(dex_names.len());
+    for dex_name in dex_names {
+        let jar_name = format!("{}.jar", &dex_name[..dex_name.len() - ".dex".len()]);
+        let jar_path = config.get_dist_folder().join(package.as_ref()).join(&jar_name);
+
+        // Command to convert .dex to .jar. using dex2jar.
+        // "-o path" to specify an output file
+        let args = vec![
+            config.get_dist_folder().join(package.as_ref()).join(dex_name).display().to_string(),
+            "-o".to_owned(),
+            jar_path.display().to_string(),
+        ];
+
+        let invocation = match run_tool(&dex2jar_script, &args, "the .dex to .jar conversion command") {
+            Ok(i) => i,
+            Err(e) => exit(e.into()),
         };
 
-        // Reading the classes.dex file.
-        let mut bytes = Vec::with_capacity(dex_file.size() as usize);
-        if let Err(e) = dex_file.read_to_end(&mut bytes) {
-            print_error(format!("There was an error while reading classes.dex file from the {}. \
-                                 More info: {}",
-                                ".apk".italic(),
-                                e),
-                        config.is_verbose());
-            exit(Error::Unknown.into());
-        }
+        benchmarks.push(Benchmark::new(format!("Dex2jar ({})", dex_name), invocation.elapsed()));
+
+        jars.push(jar_path);
+    }
 
-        if let Err(e) = out_file.write_all(&bytes[..]) {
-            print_error(format!("There was an error while writting classes.dex file. More info: \
-                                 {}",
-                                e),
-                        config.is_verbose());
+    let classes = config.get_dist_folder().join(package.as_ref()).join("classes.jar");
+    if jars.len() == 1 {
+        if jars[0] != classes {
+            if let Err(e) = fs::rename(&jars[0], &classes) {
+                error!("There was an error while renaming the .jar file. More info: {}", e);
+                exit(Error::Unknown.into());
+            }
+        }
+    } else {
+        // `classes.dex` is converted straight to `classes.jar`, so the jars are merged into a
+        // separate file first and only then moved into place.
+        let merged = config.get_dist_folder().join(package.as_ref()).join("classes.merged.jar");
+        merge_jars(&jars, &merged);
+        if let Err(e) = fs::rename(&merged, &classes) {
+            error!("There was an error while renaming the merged .jar file. More info: {}", e);
             exit(Error::Unknown.into());
         }
+    }
 
-        benchmarks.push(Benchmark::new("Dex extraction", start_time.elapsed()));
+    debug!("The application .jar file has been generated in {}", classes.display());
+}
 
-        if config.is_verbose() {
-            println!("{}",
-                     format!("The {} {}",
-                             ".dex".italic().green(),
-                             "file was extracted successfully!".green())
-                         .green());
-            println!("");
-            println!("Now it's time to create the {} file from its classes.",
-                     ".jar".italic());
-        } else if !config.is_quiet() {
-            println!("Dex file extracted.");
+/// Merges several _.jar_ files produced from a multidex APK's `classesN.dex` files into a
+/// single one, so that `decompile` sees the complete source set.
+fn merge_jars(jars: &[PathBuf], destination: &Path) {
+    let out_file = match File::create(destination) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("There was an error while creating the merged .jar file. More info: {}", e);
+            exit(Error::Unknown.into());
         }
+    };
 
-        let dex_jar_time = Instant::now();
+    let mut writer = ZipWriter::new(out_file);
+    let mut seen = HashSet::new();
 
-        // Converting the .dex to .jar.
-        dex_to_jar(config, package.as_ref());
+    for jar in jars {
+        let file = match File::open(jar) {
+            Ok(f) => f,
+            Err(e) => {
+                error!("There was an error while opening {} for merging. More info: {}",
+                      jar.display(),
+                      e);
+                exit(Error::Unknown.into());
+            }
+        };
+        let mut archive = match ZipArchive::new(file) {
+            Ok(a) => a,
+            Err(e) => {
+                error!("There was an error while reading {} for merging. More info: {}",
+                      jar.display(),
+                      e);
+                exit(Error::Unknown.into());
+            }
+        };
 
-        benchmarks.push(Benchmark::new("Dex to Jar decompilation", dex_jar_time.elapsed()));
-    } else if config.is_verbose() {
-        println!("Seems that there is already a {} file for the application. There is no need to \
-                  create it again.",
-                 ".jar".italic());
-    }
-}
+        for i in 0..archive.len() {
+            let mut entry = match archive.by_index(i) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+            if entry.is_dir() || !seen.insert(entry.name().to_owned()) {
+                continue;
+            }
 
-/// Converts _.dex_ files to _.jar_ using _Dex2jar_.
-fn dex_to_jar<S: AsRef<str>>(config: &Config, package: S) {
-    let classes = config.get_dist_folder()
-        .join(package.as_ref())
-        .join("classes.jar");
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            if entry.read_to_end(&mut bytes).is_err() {
+                continue;
+            }
 
-    // Command to convert .dex to .jar. using dex2jar.
-    // "-o path" to specify an output file
-    let output = Command::new(config.get_dex2jar_folder()
-            .join(if cfg!(target_family = "windows") {
-                "d2j-dex2jar.bat"
-            } else {
-                "d2j-dex2jar.sh"
-            }))
-        .arg(config.get_dist_folder()
-            .join(package.as_ref())
-            .join("classes.dex"))
-        .arg("-o")
-        .arg(&classes)
-        .output();
-
-    if output.is_err() {
-        print_error(format!("There was an error when executing the {} to {} conversion \
-                             command: {}",
-                            ".dex".italic(),
-                            ".jar".italic(),
-                            output.err().unwrap()),
-                    config.is_verbose());
-        exit(Error::Unknown.into());
+            if writer.start_file(entry.name(), FileOptions::default()).is_err() {
+                continue;
+            }
+            let _ = writer.write_all(&bytes);
+        }
     }
 
-    let output = output.unwrap();
-    if !output.status.success() {
-        print_error(format!("The {} to {} conversion command returned an error. More info: \
-                             {}",
-                            ".dex".italic(),
-                            ".jar".italic(),
-                            String::from_utf8_lossy(&output.stderr[..])),
-                    config.is_verbose());
+    if let Err(e) = writer.finish() {
+        error!("There was an error while finalizing the merged .jar file. More info: {}", e);
         exit(Error::Unknown.into());
     }
 
-    if config.is_verbose() {
-        println!("{}",
-                 format!("The application {} {} {}",
-                         ".jar".italic(),
-                         "file has been generated in".green(),
-                         format!("{}", classes.display()).green())
-                     .green());
-    } else if !config.is_quiet() {
-        println!("Jar file generated.");
+    for jar in jars {
+        let _ = fs::remove_file(jar);
     }
 }
 
 /// Decompiles the application using _jd\_cmd_.
-pub fn decompile<S: AsRef<str>>(config: &Config, package: S) {
+pub fn decompile<S: AsRef<str>>(config: &Config, package: S, benchmarks: &mut Vec<Benchmark>) {
     let out_path = config.get_dist_folder()
         .join(package.as_ref())
         .join("classes");
     if config.is_force() || !out_path.exists() {
         // Command to decompile the application using jd_cmd.
         // "-od path" to specify an output directory
-        let output = Command::new("java")
-            .arg("-jar")
-            .arg(config.get_jd_cmd_file())
-            .arg(config.get_dist_folder()
-                .join(package.as_ref())
-                .join("classes.jar"))
-            .arg("-od")
-            .arg(&out_path)
-            .output();
-
-        if output.is_err() {
-            print_error(format!("There was an unknown error decompiling the application: {}",
-                                output.err().unwrap()),
-                        config.is_verbose());
-            exit(Error::Unknown.into());
-        }
+        let args = vec![
+            "-jar".to_owned(),
+            config.get_jd_cmd_file().display().to_string(),
+            config.get_dist_folder().join(package.as_ref()).join("classes.jar").display().to_string(),
+            "-od".to_owned(),
+            out_path.display().to_string(),
+        ];
 
-        let output = output.unwrap();
-        if !output.status.success() {
-            print_error(format!("The decompilation command returned an error. More info: {}",
-                                String::from_utf8_lossy(&output.stderr[..])),
-                        config.is_verbose());
-            exit(Error::Unknown.into());
-        }
+        let invocation = match run_tool(Path::new("java"), &args, "the decompilation command") {
+            Ok(i) => i,
+            Err(e) => exit(e.into()),
+        };
+
+        benchmarks.push(Benchmark::new("Jd-cmd decompilation", invocation.elapsed()));
+
+        debug!("The application has been succesfully decompiled!");
+    } else {
+        debug!("Seems that there is already a source folder for the application. There is no \
+                need to decompile it again.");
+    }
+}
+
+/// Decompilation helpers testing module.
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::time::Duration;
 
-        if config.is_verbose() {
-            println!("{}",
-                     "The application has been succesfully decompiled!".green());
-        } else if !config.is_quiet() {
-            println!("Application decompiled.");
+    use super::{classify_failure, dex_index, is_dex_entry, is_java, ToolInvocation};
+    use Error;
+
+    /// Test that multidex `classesN.dex` entries are recognized.
+    #[test]
+    fn it_is_dex_entry() {
+        assert!(is_dex_entry("classes.dex"));
+        assert!(is_dex_entry("classes2.dex"));
+        assert!(is_dex_entry("classes10.dex"));
+        assert!(!is_dex_entry("AndroidManifest.xml"));
+        assert!(!is_dex_entry("classes.dex.orig"));
+    }
+
+    /// Test that `classesN.dex` entries map to their multidex index, which `extract_dex` sorts by.
+    #[test]
+    fn it_dex_index() {
+        assert_eq!(dex_index("classes.dex"), Some(0));
+        assert_eq!(dex_index("classes2.dex"), Some(2));
+        assert_eq!(dex_index("classes10.dex"), Some(10));
+        assert_eq!(dex_index("AndroidManifest.xml"), None);
+    }
+
+    /// Test that only the `java` binary itself is classified as such.
+    #[test]
+    fn it_is_java() {
+        assert!(is_java(Path::new("java")));
+        assert!(is_java(Path::new("/usr/bin/java")));
+        assert!(!is_java(Path::new("d2j-dex2jar.sh")));
+    }
+
+    /// Builds a `ToolInvocation` with the given stderr, for `classify_failure` tests.
+    fn invocation_with_stderr(stderr: &str) -> ToolInvocation {
+        ToolInvocation {
+            binary: "java".to_owned(),
+            args: Vec::new(),
+            exit_code: Some(1),
+            stdout: String::new(),
+            stderr: stderr.to_owned(),
+            elapsed: Duration::default(),
         }
-    } else if config.is_verbose() {
-        println!("Seems that there is already a source folder for the application. There is no \
-                  need to decompile it again.");
+    }
+
+    /// Test that common failure signatures are classified instead of falling back to `Unknown`.
+    #[test]
+    fn it_classify_failure() {
+        assert_eq!(
+            classify_failure(&invocation_with_stderr("Error occurred during initialization of VM\n\
+                                                        java.lang.OutOfMemoryError")),
+            Error::OutOfMemory
+        );
+        assert_eq!(
+            classify_failure(&invocation_with_stderr("Unsupported dex version")),
+            Error::UnsupportedDexVersion
+        );
+        assert_eq!(
+            classify_failure(&invocation_with_stderr("zip END header not found")),
+            Error::CorruptApk
+        );
+        assert_eq!(
+            classify_failure(&invocation_with_stderr("something else entirely")),
+            Error::Unknown
+        );
     }
 }
@@ -0,0 +1,54 @@
+//! Error module.
+//!
+//! Defines the process-level `Error` type used by the decompilation pipeline to pick an exit
+//! code when a step cannot continue.
+
+use std::fmt;
+
+/// Process-level error, used to classify why a decompilation step aborted and to choose the
+/// exit code the process terminates with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// An error whose cause doesn't fit any of the other variants.
+    Unknown,
+    /// The `java` binary could not be found or executed.
+    MissingJava,
+    /// An external tool (other than `java` itself) could not be found or executed.
+    MissingTool,
+    /// A Java tool ran out of memory while running.
+    OutOfMemory,
+    /// The analyzed `.apk` is corrupt, encrypted or otherwise unreadable.
+    CorruptApk,
+    /// The `.apk` contains a `.dex` file with an unsupported format version.
+    UnsupportedDexVersion,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Unknown => write!(f, "an unknown error occurred"),
+            Error::MissingJava => write!(f, "the `java` binary could not be found or executed"),
+            Error::MissingTool => write!(f, "an external tool could not be found or executed"),
+            Error::OutOfMemory => write!(f, "a Java tool ran out of memory"),
+            Error::CorruptApk => {
+                write!(f, "the .apk file is corrupt, encrypted or otherwise unreadable")
+            }
+            Error::UnsupportedDexVersion => {
+                write!(f, "the .apk contains a .dex file with an unsupported format version")
+            }
+        }
+    }
+}
+
+impl From<Error> for i32 {
+    fn from(error: Error) -> i32 {
+        match error {
+            Error::Unknown => 1,
+            Error::MissingJava => 2,
+            Error::MissingTool => 3,
+            Error::OutOfMemory => 4,
+            Error::CorruptApk => 5,
+            Error::UnsupportedDexVersion => 6,
+        }
+    }
+}
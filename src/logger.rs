@@ -0,0 +1,91 @@
+//! Logging subsystem.
+//!
+//! Builds the `log`/`env_logger` backend used by the rest of the crate, mapping the verbose/
+//! quiet flags to a log level and tee-ing every log line into a per-package log file alongside
+//! the usual `stderr` output.
+//!
+//! `env_logger` can only be installed once per process, but each analyzed package needs its
+//! own timestamped log file. [`init`] installs the backend once; [`set_log_file`] repoints its
+//! file output before each package is analyzed.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, Once};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use env_logger::{Builder, Target};
+use log::LevelFilter;
+
+static INIT: Once = Once::new();
+static CURRENT_LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Writes every log line to `stderr`, and additionally to whichever file [`set_log_file`] last
+/// pointed at, if any.
+struct Tee;
+
+impl Write for Tee {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let _ = io::stderr().write_all(buf);
+        if let Ok(mut file) = CURRENT_LOG_FILE.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.write_all(buf);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()?;
+        if let Ok(mut file) = CURRENT_LOG_FILE.lock() {
+            if let Some(file) = file.as_mut() {
+                file.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Installs the `log` backend, mapping `verbose`/`quiet` to `Debug`/`Error`/`Info`.
+///
+/// Only the first call in a process actually installs the logger and its level; later calls
+/// are no-ops. Call [`set_log_file`] before analyzing each package so it gets its own
+/// timestamped log file regardless of how many times `init` itself is called.
+pub fn init(verbose: bool, quiet: bool) {
+    INIT.call_once(|| {
+        let level = if verbose {
+            LevelFilter::Debug
+        } else if quiet {
+            LevelFilter::Error
+        } else {
+            LevelFilter::Info
+        };
+
+        let mut builder = Builder::new();
+        let _ = builder.filter_level(level);
+        let _ = builder.target(Target::Pipe(Box::new(Tee)));
+        let _ = builder.try_init();
+    });
+}
+
+/// Points the logger's file output at a fresh `super_<timestamp>.log` file inside `log_dir`,
+/// so the package about to be analyzed gets its own log file. Pass `None` to stop logging to
+/// a file (e.g. when log-to-file is disabled in the configuration).
+pub fn set_log_file(log_dir: Option<&Path>) {
+    let file = log_dir.and_then(create_log_file);
+    if let Ok(mut current) = CURRENT_LOG_FILE.lock() {
+        *current = file;
+    }
+}
+
+/// Creates the timestamped log file inside `log_dir`, creating the directory if needed.
+fn create_log_file(log_dir: &Path) -> Option<File> {
+    fs::create_dir_all(log_dir).ok()?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    File::create(log_dir.join(format!("super_{}.log", timestamp))).ok()
+}
@@ -10,6 +10,7 @@ use std::{
 use colored::Colorize;
 use failure::{Error, ResultExt};
 use handlebars::Handlebars;
+use rust_embed::RustEmbed;
 use serde_json::{value::Value, Map};
 
 use crate::{
@@ -25,6 +26,12 @@ use crate::{
     },
 };
 
+/// Default templates, compiled into the binary so that a freshly installed `super` can
+/// generate reports without a `templates` directory on disk.
+#[derive(RustEmbed)]
+#[folder = "templates"]
+struct EmbeddedTemplates;
+
 /// Handlebars report generator.
 pub struct Report {
     /// Handlebars template structure.
@@ -35,12 +42,17 @@ pub struct Report {
 
 impl Report {
     /// Creates a new handlebars report generator.
+    ///
+    /// Templates are loaded from `template_path` when it exists and provides the `report`,
+    /// `src` and `code` templates. Otherwise, the templates embedded in the binary are used,
+    /// so the on-disk path only needs to be provided to customize the report's theme.
     pub fn from_path<P: AsRef<Path>, S: Into<String>>(
         template_path: P,
         package: S,
     ) -> Result<Self, Error> {
-        let handlebars_handler =
-            Self::load_templates(template_path).context("Could not load handlebars templates")?;
+        let handlebars_handler = Self::load_templates(template_path.as_ref())
+            .or_else(|_| Self::load_embedded_templates())
+            .context("Could not load handlebars templates")?;
 
         Ok(Self {
             handler: handlebars_handler,
@@ -48,9 +60,8 @@ impl Report {
         })
     }
 
-    /// Loads templates from the given path.
-    fn load_templates<P: AsRef<Path>>(template_path: P) -> Result<Handlebars, Error> {
-        let mut handlebars = Handlebars::new();
+    /// Registers the helpers shared by on-disk and embedded templates.
+    fn register_helpers(handlebars: &mut Handlebars) {
         handlebars.register_escape_fn(|s| html_escape(s).into_owned());
         let _ = handlebars.register_helper("line_numbers", Box::new(line_numbers));
         let _ = handlebars.register_helper("html_code", Box::new(html_code));
@@ -58,6 +69,31 @@ impl Report {
         let _ = handlebars.register_helper("all_code", Box::new(all_code));
         let _ = handlebars.register_helper("all_lines", Box::new(all_lines));
         let _ = handlebars.register_helper("generate_menu", Box::new(generate_menu));
+    }
+
+    /// Checks that the `report`, `src` and `code` templates were all registered.
+    fn check_required_templates(handlebars: &Handlebars) -> Result<(), Error> {
+        if handlebars.get_template("report").is_none()
+            || handlebars.get_template("src").is_none()
+            || handlebars.get_template("code").is_none()
+        {
+            let message = format!(
+                "templates must include {}, {} and {} templates",
+                "report".italic(),
+                "src".italic(),
+                "code".italic()
+            );
+
+            Err(error::Kind::TemplateName { message }.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Loads templates from the given path.
+    fn load_templates<P: AsRef<Path>>(template_path: P) -> Result<Handlebars, Error> {
+        let mut handlebars = Handlebars::new();
+        Self::register_helpers(&mut handlebars);
         for dir_entry in fs::read_dir(template_path)? {
             let dir_entry = dir_entry?;
             if let Some(ext) = dir_entry.path().extension() {
@@ -81,21 +117,44 @@ impl Report {
             }
         }
 
-        if handlebars.get_template("report").is_none()
-            || handlebars.get_template("src").is_none()
-            || handlebars.get_template("code").is_none()
-        {
-            let message = format!(
-                "templates must include {}, {} and {} templates",
-                "report".italic(),
-                "src".italic(),
-                "code".italic()
-            );
+        Self::check_required_templates(&handlebars)?;
+        Ok(handlebars)
+    }
 
-            Err(error::Kind::TemplateName { message }.into())
-        } else {
-            Ok(handlebars)
+    /// Loads the default templates embedded in the binary.
+    fn load_embedded_templates() -> Result<Handlebars, Error> {
+        let mut handlebars = Handlebars::new();
+        Self::register_helpers(&mut handlebars);
+        for file_name in EmbeddedTemplates::iter() {
+            let file_path = Path::new(file_name.as_ref());
+            if file_path.extension().map(|ext| ext == "hbs") != Some(true) {
+                continue;
+            }
+
+            let template_file = file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| error::Kind::TemplateName {
+                    message: "embedded template names must be unicode".to_string(),
+                })?;
+
+            let contents = EmbeddedTemplates::get(&file_name).ok_or_else(|| {
+                error::Kind::TemplateName {
+                    message: format!("could not load embedded template {}", file_name),
+                }
+            })?;
+            let template_str = std::str::from_utf8(contents.as_ref())
+                .map_err(|_| error::Kind::TemplateName {
+                    message: "embedded templates must be valid utf-8".to_string(),
+                })?;
+
+            handlebars
+                .register_template_string(template_file, template_str)
+                .context("error registering embedded template")?;
         }
+
+        Self::check_required_templates(&handlebars)?;
+        Ok(handlebars)
     }
 
     /// Generates the HTML files for the code.
@@ -257,26 +316,31 @@ impl Generator for Report {
 
         f.write_all(self.handler.render("report", results)?.as_bytes())?;
 
-        for entry in fs::read_dir(config.template_path())? {
-            let entry = entry?;
-            let entry_path = entry.path();
-            if entry.file_type()?.is_dir() {
-                copy_folder(
-                    &entry_path,
-                    &config
-                        .results_folder()
-                        .join(&results.app_package())
-                        .join(entry_path.file_name().unwrap()),
-                )?;
-            } else {
-                match entry_path.as_path().extension() {
-                    Some(e) if e == "hbs" => {}
-                    None => {}
-                    _ => {
-                        let _ = fs::copy(
-                            &entry_path,
-                            &config.results_folder().join(&results.app_package()),
-                        )?;
+        // Non-template assets are only copied from an on-disk `template_path`; when that path
+        // doesn't exist, the report is rendered from the embedded templates and no assets are
+        // copied.
+        if config.template_path().exists() {
+            for entry in fs::read_dir(config.template_path())? {
+                let entry = entry?;
+                let entry_path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    copy_folder(
+                        &entry_path,
+                        &config
+                            .results_folder()
+                            .join(&results.app_package())
+                            .join(entry_path.file_name().unwrap()),
+                    )?;
+                } else {
+                    match entry_path.as_path().extension() {
+                        Some(e) if e == "hbs" => {}
+                        None => {}
+                        _ => {
+                            let _ = fs::copy(
+                                &entry_path,
+                                &config.results_folder().join(&results.app_package()),
+                            )?;
+                        }
                     }
                 }
             }
@@ -300,10 +364,10 @@ mod test {
         let _ = Report::from_path(&Config::default().template_path(), "test").unwrap();
     }
 
-    /// Test the failure of the creation of an invalid new report.
+    /// Test that a missing template path falls back to the embedded templates.
     #[test]
-    fn it_new_failure() {
-        assert!(Report::from_path("random path", "test").is_err());
+    fn it_new_fallback_to_embedded() {
+        let _ = Report::from_path("random path", "test").unwrap();
     }
 
     /// Tests handlebars template loading.
@@ -311,4 +375,10 @@ mod test {
     fn it_load_templates() {
         let _ = Report::load_templates(&Config::default().template_path()).unwrap();
     }
+
+    /// Tests that the templates embedded in the binary load on their own.
+    #[test]
+    fn it_load_embedded_templates() {
+        let _ = Report::load_embedded_templates().unwrap();
+    }
 }
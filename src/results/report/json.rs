@@ -0,0 +1,147 @@
+//! JSON and SARIF report generation module.
+
+use std::fs::File;
+use std::io::Write;
+
+use failure::{Error, ResultExt};
+use serde_json::{json, Value};
+
+use crate::{config::Config, results::report::Generator, results::Results};
+
+/// JSON/SARIF report generator.
+///
+/// Writes `report.json`, a plain serialization of the analysis [`Results`], and
+/// `report.sarif`, a [SARIF](https://sarifweb.azurewebsites.net/)-compatible document, next to
+/// `index.html`. This lets CI pipelines gate builds on findings or feed them straight into
+/// GitHub code scanning and other security dashboards.
+#[derive(Debug, Default)]
+pub struct JsonReport;
+
+impl JsonReport {
+    /// Creates a new JSON/SARIF report generator.
+    pub fn new() -> Self {
+        JsonReport::default()
+    }
+
+    /// Builds the SARIF document for the given results.
+    ///
+    /// `Vulnerability`'s typed definition isn't in scope here, so fields are pulled out of the
+    /// serialized `Value` by name rather than through its accessors; [`string_field`] and
+    /// [`line_field`] make sure a field that's present but `null` falls back the same way a
+    /// missing one does, instead of leaking a literal `null` into the SARIF document.
+    fn to_sarif(results: &Results) -> Result<Value, Error> {
+        let value = serde_json::to_value(results).context("could not serialize results")?;
+        let vulnerabilities = value
+            .get("vulnerabilities")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        let rules: Vec<Value> = vulnerabilities
+            .iter()
+            .map(|vuln| {
+                json!({
+                    "id": Self::string_field(vuln, "name"),
+                    "shortDescription": {
+                        "text": Self::string_field(vuln, "description"),
+                    },
+                })
+            })
+            .collect();
+
+        let sarif_results: Vec<Value> = vulnerabilities
+            .iter()
+            .map(|vuln| {
+                json!({
+                    "ruleId": Self::string_field(vuln, "name"),
+                    "level": Self::sarif_level(vuln.get("criticality").and_then(Value::as_str)),
+                    "message": {
+                        "text": Self::string_field(vuln, "description"),
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": Self::string_field(vuln, "file"),
+                            },
+                            "region": {
+                                "startLine": Self::line_field(vuln, "line"),
+                            },
+                        },
+                    }],
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "SUPER",
+                        "rules": rules,
+                    },
+                },
+                "results": sarif_results,
+            }],
+        }))
+    }
+
+    /// Maps SUPER's criticality levels to the SARIF `level` property.
+    fn sarif_level(criticality: Option<&str>) -> &'static str {
+        match criticality {
+            Some("Critical") | Some("High") => "error",
+            Some("Medium") => "warning",
+            _ => "note",
+        }
+    }
+
+    /// Reads a string field off a serialized vulnerability, treating both a missing field and a
+    /// `null` one as an empty string so neither can leak a literal `null` into the SARIF output.
+    fn string_field(vuln: &Value, field: &str) -> Value {
+        Value::String(
+            vuln.get(field)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+        )
+    }
+
+    /// Reads the `line` field off a serialized vulnerability, defaulting to `1` (SARIF requires
+    /// a positive line number) whenever it's missing, `null`, or not a valid line number.
+    fn line_field(vuln: &Value, field: &str) -> Value {
+        Value::from(
+            vuln.get(field)
+                .and_then(Value::as_u64)
+                .filter(|&line| line > 0)
+                .unwrap_or(1),
+        )
+    }
+}
+
+impl Generator for JsonReport {
+    fn generate(&mut self, config: &Config, results: &Results) -> Result<(), Error> {
+        let app_path = config.results_folder().join(&results.app_package());
+
+        let json_value = serde_json::to_value(results).context("could not serialize results")?;
+        let mut json_file = File::create(app_path.join("report.json"))?;
+        json_file.write_all(serde_json::to_string_pretty(&json_value)?.as_bytes())?;
+
+        let sarif = Self::to_sarif(results)?;
+        let mut sarif_file = File::create(app_path.join("report.sarif"))?;
+        sarif_file.write_all(serde_json::to_string_pretty(&sarif)?.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JsonReport;
+
+    /// Test the creation of a new JSON report generator.
+    #[test]
+    fn it_new() {
+        let _ = JsonReport::new();
+    }
+}
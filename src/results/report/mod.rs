@@ -0,0 +1,34 @@
+//! Report generation module.
+//!
+//! Exposes the `Generator` trait implemented by each report backend, and picks which backend
+//! to run based on the current `Config`.
+
+pub mod handlebars;
+pub mod json;
+
+use failure::Error;
+
+use crate::config::Config;
+use crate::results::Results;
+
+pub use self::handlebars::Report;
+pub use self::json::JsonReport;
+
+/// A report backend able to turn analysis [`Results`] into a report on disk.
+pub trait Generator {
+    /// Generates the report for `results` using `config`.
+    fn generate(&mut self, config: &Config, results: &Results) -> Result<(), Error>;
+}
+
+/// Generates the report for `results`, picking the backend selected in `config`.
+///
+/// Defaults to the HTML/Handlebars report; `config.is_json_report()` switches to the
+/// JSON/SARIF report instead, for consumption by CI pipelines and code scanning dashboards.
+pub fn generate(config: &Config, results: &Results) -> Result<(), Error> {
+    if config.is_json_report() {
+        JsonReport::new().generate(config, results)
+    } else {
+        Report::from_path(config.template_path(), results.app_package())?
+            .generate(config, results)
+    }
+}
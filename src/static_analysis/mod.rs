@@ -1,15 +1,19 @@
 mod manifest;
 mod code;
 
+use log::debug;
+
 use self::manifest::*;
 // use self::code::*;
 
+/// Runs the static analysis over `app_id`'s decompiled sources.
+///
+/// `verbose`/`quiet` are only passed on to `manifest_analysis`, which takes them directly
+/// rather than going through `log`.
 pub fn static_analysis(app_id: &str, verbose: bool, quiet: bool) {
-    if verbose {
-        println!("It's time to analyse the application. First, a static analysis will be \
-                  performed, starting with the AndroidManifest.xml file and then going through \
-                  the actual code. Let's start!");
-    }
+    debug!("It's time to analyse the application. First, a static analysis will be performed, \
+            starting with the AndroidManifest.xml file and then going through the actual code. \
+            Let's start!");
 
     manifest_analysis(app_id, verbose, quiet);
     // TODO Code analysis